@@ -2,17 +2,48 @@
  	An edit control is a rectangular control window to permit the user to enter and edit text by typing on the keyboard
 */
 use winapi::shared::minwindef::{UINT, WPARAM, LPARAM};
+use winapi::shared::windef::HBRUSH;
 use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, ES_NUMBER, ES_LEFT, ES_CENTER, ES_RIGHT, ES_AUTOHSCROLL};
 use crate::win32::window_helper as wh;
 use crate::{Font, NwgError, HTextAlign, RawEventHandler};
 use super::{ControlBase, ControlHandle};
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::ops::Range;
+use std::rc::Rc;
 use std::char;
 
 const NOT_BOUND: &'static str = "TextInput is not yet bound to a winapi object";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: TextInput handle is not HWND!";
 
+/// Base DPI used as the reference for the default (96 DPI) vertical centering offset
+const BASE_DPI: i32 = 96;
+
+/// Return the DPI of the monitor the window is currently on.
+/// Uses `GetDpiForWindow` (Per-Monitor-V2, Windows 10 1607+), looked up at runtime in user32,
+/// and falls back to the system wide DPI on older versions of Windows where it is unavailable.
+unsafe fn dpi_for_window(hwnd: ::winapi::shared::windef::HWND) -> i32 {
+    use winapi::um::libloaderapi::{GetModuleHandleA, GetProcAddress};
+
+    type GetDpiForWindowFn = unsafe extern "system" fn(::winapi::shared::windef::HWND) -> UINT;
+    let user32 = GetModuleHandleA(b"user32.dll\0".as_ptr() as _);
+    let proc = if user32.is_null() { ::std::ptr::null_mut() } else { GetProcAddress(user32, b"GetDpiForWindow\0".as_ptr() as _) };
+    if !proc.is_null() {
+        let dpi = ::std::mem::transmute::<_, GetDpiForWindowFn>(proc)(hwnd);
+        if dpi > 0 {
+            return dpi as i32;
+        }
+    }
+
+    use winapi::um::winuser::{GetDC, ReleaseDC};
+    use winapi::um::wingdi::{GetDeviceCaps, LOGPIXELSY};
+
+    let dc = GetDC(hwnd);
+    let dpi = GetDeviceCaps(dc, LOGPIXELSY);
+    ReleaseDC(hwnd, dc);
+
+    if dpi > 0 { dpi } else { BASE_DPI }
+}
+
 
 bitflags! {
     pub struct TextInputFlags: u32 {
@@ -23,6 +54,30 @@ bitflags! {
     }
 }
 
+/// Character filtering applied to a `TextInput` by `TextInput::set_mask`.
+/// This generalizes the `ES_NUMBER` style (the edit control only half enforces it, it still
+/// lets through punctuation on some system locales) to arbitrary presets and templates.
+#[derive(Clone)]
+pub enum Mask {
+    /// No filtering. The default.
+    None,
+    /// Only ASCII digits (`0`-`9`) are accepted.
+    Digits,
+    /// Only ASCII letters and digits are accepted.
+    Alphanumeric,
+    /// A custom predicate deciding if a character is accepted.
+    Custom(Rc<dyn Fn(char) -> bool>),
+    /// A fixed template such as `"(###) ###-####"` or `"##/##/####"`.
+    /// `#` accepts a digit, `@` accepts a letter, `*` accepts a letter or digit.
+    /// Any other character is a literal that's automatically inserted and skipped over
+    /// as the user types or pastes text.
+    Template(&'static str),
+}
+
+impl Default for Mask {
+    fn default() -> Mask { Mask::None }
+}
+
 /** 
 An edit control is a rectangular control window to permit the user to enter and edit text by typing on the keyboard
 This control only allow a single line input. For block of text, use `TextBox`.
@@ -42,11 +97,16 @@ TextInput is not behind any features.
   * `password`:         The password character. If set to None, the textinput is a regular control.
   * `align`:            The alignment of the text in the text input
   * `background_color`: The color of the textinput top and bottom padding. This is not the white background under the text.
+  * `placeholder`:      The grey prompt text shown while the control is empty. Requires the application to be linked against Comctl32 v6 (see the application manifest documentation).
+  * `dark_mode`:        If the control should use the dark system appearance instead of the default light one. In dark mode, `background_color` (if set) is also the background under the text.
 
 **Control events:**
   * `OnTextInput`: When a TextInput value is changed
   * `MousePress(_)`: Generic mouse press events on the button
   * `OnMouseMove`: Generic mouse mouse event
+  * `OnFileDrop`: When one or more files are dropped on the TextInput
+  * `OnTextDrop`: When text is dropped on the TextInput
+  * `OnTextInputInvalid`: When a keystroke or paste is rejected by the mask set with `set_mask`
 
 ```rust
 use native_windows_gui as nwg;
@@ -63,6 +123,21 @@ fn build_box(tbox: &mut nwg::TextInput, window: &nwg::Window, font: &nwg::Font)
 pub struct TextInput {
     pub handle: ControlHandle,
     handler0: RefCell<Option<RawEventHandler>>,
+    dark_mode: Rc<Cell<bool>>,
+    drop_target: RefCell<Option<*mut ::winapi::um::oleidl::IDropTarget>>,
+    mask: Rc<RefCell<Mask>>,
+    handler1: RefCell<Option<RawEventHandler>>,
+    /// The color override passed to the builder, kept around so `set_dark_mode` can create
+    /// the dark brush lazily with the same color the light-mode brush already uses.
+    background_color: Cell<Option<[u8; 3]>>,
+    /// Only set (and only freed in `Drop`) when `background_color` is `Some`; the `None` case
+    /// paints with the `COLOR_WINDOW` stock color, which isn't a handle we own.
+    background_brush: Cell<HBRUSH>,
+    /// Lazily created by `hook_dark_mode_color` the first time `set_dark_mode(true)` runs.
+    dark_brush: Cell<HBRUSH>,
+    /// Subclasses the *parent* window to answer `WM_CTLCOLOREDIT`/`WM_CTLCOLORSTATIC`, which
+    /// Windows sends to the parent, not to the edit control itself.
+    handler2: RefCell<Option<RawEventHandler>>,
 }
 
 impl TextInput {
@@ -79,7 +154,152 @@ impl TextInput {
             readonly: false,
             font: None,
             parent: None,
-            background_color: None
+            background_color: None,
+            placeholder: None,
+            dark_mode: false,
+            mask: Mask::None,
+        }
+    }
+
+    /// Return the placeholder (cue banner) text shown while the control is empty.
+    /// Return `None` if no placeholder is set.
+    pub fn placeholder(&self) -> Option<String> {
+        use winapi::um::commctrl::EM_GETCUEBANNER;
+
+        if self.handle.blank() { panic!(NOT_BOUND); }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+
+        let mut buffer: [u16; 256] = [0; 256];
+        let has_banner = wh::send_message(handle, EM_GETCUEBANNER as u32, buffer.as_mut_ptr() as WPARAM, buffer.len() as LPARAM);
+        if has_banner == 0 {
+            return None;
+        }
+
+        // `set_placeholder(None)` stores an empty banner, which reads back as no placeholder
+        let end = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        if end == 0 {
+            return None;
+        }
+
+        Some(String::from_utf16_lossy(&buffer[..end]))
+    }
+
+    /// Set or remove the placeholder (cue banner) text displayed while the control is empty.
+    /// The banner is also shown while the control has focus but is empty.
+    ///
+    /// This is implemented with `EM_SETCUEBANNER` which requires the application to be linked
+    /// against Comctl32 v6 (see the application manifest documentation). If the common controls
+    /// were not initialized this way, this call is a no-op.
+    pub fn set_placeholder(&self, text: Option<&str>) {
+        use winapi::um::commctrl::EM_SETCUEBANNER;
+
+        if self.handle.blank() { panic!(NOT_BOUND); }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+
+        let mut buffer: Vec<u16> = text.unwrap_or("").encode_utf16().collect();
+        buffer.push(0);
+
+        wh::send_message(handle, EM_SETCUEBANNER as u32, 1, buffer.as_ptr() as LPARAM);
+    }
+
+    /// Return true if the control uses the dark system appearance
+    pub fn dark_mode(&self) -> bool {
+        self.dark_mode.get()
+    }
+
+    /// Enable or disable the dark system appearance on the control.
+    /// This calls `SetWindowTheme` with `"DarkMode_Explorer"` so the border and scrollbar
+    /// follow the system dark theme, and, the first time it's turned on, subclasses the
+    /// parent window (see `hook_dark_mode_color`) to answer `WM_CTLCOLOREDIT`/
+    /// `WM_CTLCOLORSTATIC` for this control with a dark background/text color.
+    pub fn set_dark_mode(&self, dark: bool) {
+        use winapi::um::uxtheme::SetWindowTheme;
+        use winapi::shared::ntdef::LPCWSTR;
+        use std::ptr;
+
+        if self.handle.blank() { panic!(NOT_BOUND); }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+
+        self.dark_mode.set(dark);
+
+        let theme_name = if dark { "DarkMode_Explorer" } else { "Explorer" };
+        let theme: Vec<u16> = theme_name.encode_utf16().chain(Some(0)).collect();
+
+        unsafe { SetWindowTheme(handle, theme.as_ptr() as LPCWSTR, ptr::null()); }
+
+        if dark && self.handler2.borrow().is_none() {
+            self.hook_dark_mode_color();
+        }
+    }
+
+    /// Subclass the control's *parent* window to answer `WM_CTLCOLOREDIT`/
+    /// `WM_CTLCOLORSTATIC` for this control. Windows sends these two messages to the parent
+    /// of an edit control, not to the edit control itself, so this can't be folded into
+    /// `hook_non_client_size`, which subclasses `self.handle`. Bound lazily the first time
+    /// `set_dark_mode(true)` is called; the dark brush it creates lives until `Drop`.
+    ///
+    /// The subclass id is derived from this control's own HWND: the parent is shared with
+    /// any sibling controls, and `SetWindowSubclass` replaces the reference data of an
+    /// existing `(hwnd, id)` subclass rather than stacking a new one, so two siblings both
+    /// using a fixed id would clobber each other's hook on the shared parent.
+    fn hook_dark_mode_color(&self) {
+        use crate::bind_raw_event_handler;
+        use winapi::shared::windef::{HDC, HWND};
+        use winapi::um::winuser::{GetParent, WM_CTLCOLOREDIT, WM_CTLCOLORSTATIC};
+        use winapi::um::wingdi::{CreateSolidBrush, SetBkColor, SetTextColor, RGB};
+
+        if self.handle.blank() { panic!(NOT_BOUND); }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+
+        let bg = self.background_color.get();
+        let dark_brush = match bg {
+            Some(c) => unsafe { CreateSolidBrush(RGB(c[0], c[1], c[2])) },
+            None => unsafe { CreateSolidBrush(RGB(32, 32, 32)) }
+        };
+        let dark_bk_color = bg.map(|c| RGB(c[0], c[1], c[2])).unwrap_or(RGB(32, 32, 32));
+        self.dark_brush.set(dark_brush);
+
+        let dark_mode = self.dark_mode.clone();
+
+        unsafe {
+
+        let parent = GetParent(handle);
+
+        let handler = bind_raw_event_handler(&ControlHandle::Hwnd(parent), handle as usize, move |_hwnd, msg, w, l| {
+            match msg {
+                WM_CTLCOLOREDIT | WM_CTLCOLORSTATIC if dark_mode.get() && (l as HWND) == handle => {
+                    let hdc = w as HDC;
+                    SetBkColor(hdc, dark_bk_color);
+                    SetTextColor(hdc, RGB(255, 255, 255));
+                    return Some(dark_brush as isize);
+                },
+                _ => {}
+            }
+
+            None
+        });
+
+        *self.handler2.borrow_mut() = Some(handler);
+
+        }
+    }
+
+    /// Return a clone of the mask currently applied to the control
+    pub fn mask(&self) -> Mask {
+        self.mask.borrow().clone()
+    }
+
+    /// Restrict the characters the user can type or paste into the control.
+    /// See `Mask` for the available presets, custom predicates and templates.
+    /// Rejected input is swallowed and fires `OnTextInputInvalid`.
+    pub fn set_mask(&self, mask: Mask) {
+        if self.handle.blank() { panic!(NOT_BOUND); }
+        self.handle.hwnd().expect(BAD_HANDLE);
+
+        *self.mask.borrow_mut() = mask;
+
+        if self.handler1.borrow().is_none() {
+            self.hook_mask_handler();
         }
     }
 
@@ -338,8 +558,8 @@ impl TextInput {
     /// Center the text vertically. Can't believe that must be manually hacked in.
     fn hook_non_client_size(&self, bg: Option<[u8; 3]>) {
         use crate::bind_raw_event_handler;
-        use winapi::shared::windef::{HGDIOBJ, RECT, HBRUSH, POINT};
-        use winapi::um::winuser::{WM_NCCALCSIZE, WM_NCPAINT, WM_SIZE, DT_CALCRECT, DT_LEFT, NCCALCSIZE_PARAMS, COLOR_WINDOW,};
+        use winapi::shared::windef::{HGDIOBJ, RECT, POINT};
+        use winapi::um::winuser::{WM_NCCALCSIZE, WM_NCPAINT, WM_SIZE, WM_DPICHANGED_AFTERPARENT, DT_CALCRECT, DT_LEFT, NCCALCSIZE_PARAMS, COLOR_WINDOW,};
         use winapi::um::winuser::{SWP_NOOWNERZORDER, SWP_NOSIZE, SWP_NOMOVE, SWP_FRAMECHANGED};
         use winapi::um::winuser::{GetDC, DrawTextW, ReleaseDC, GetClientRect, GetWindowRect, FillRect, ScreenToClient, SetWindowPos};
         use winapi::um::wingdi::{SelectObject, CreateSolidBrush, RGB};
@@ -348,8 +568,14 @@ impl TextInput {
         if self.handle.blank() { panic!(NOT_BOUND); }
         self.handle.hwnd().expect(BAD_HANDLE);
 
+        self.background_color.set(bg);
+
         let brush = match bg {
-            Some(c) => unsafe { CreateSolidBrush(RGB(c[0], c[1], c[2])) },
+            Some(c) => {
+                let brush = unsafe { CreateSolidBrush(RGB(c[0], c[1], c[2])) };
+                self.background_brush.set(brush);
+                brush
+            },
             None => COLOR_WINDOW as HBRUSH
         };
 
@@ -380,9 +606,14 @@ impl TextInput {
                     GetClientRect(hwnd, &mut client);
                     GetWindowRect(hwnd, &mut window);
 
+                    // The offset is expressed in 96 DPI (100%) pixels, scale it to the
+                    // monitor the control currently lives on so it doesn't clip at higher DPIs.
+                    let dpi = dpi_for_window(hwnd);
+                    let offset = (4 * dpi) / BASE_DPI;
+
                     let window_height = window.bottom - window.top;
-                    let center = ((window_height - client_height) / 2) - 4;
-                    
+                    let center = ((window_height - client_height) / 2) - offset;
+
                     // Save the info
                     let info_ptr: *mut NCCALCSIZE_PARAMS = l as *mut NCCALCSIZE_PARAMS;
                     let info = &mut *info_ptr;
@@ -421,7 +652,12 @@ impl TextInput {
                     FillRect(dc, &bottom, brush);
                     ReleaseDC(hwnd, dc);
                 },
-                WM_SIZE => {
+                WM_SIZE | WM_DPICHANGED_AFTERPARENT => {
+                    // TextInput is WS_CHILD, so Per-Monitor-V2 never delivers a plain
+                    // WM_DPICHANGED to it (that only reaches top-level windows) -- the
+                    // parent gets WM_DPICHANGED and this control gets
+                    // WM_DPICHANGED_AFTERPARENT once the parent has finished resizing.
+                    // Forces a WM_NCCALCSIZE so the vertical centering is redone with the new DPI
                     SetWindowPos(hwnd, ptr::null_mut(), 0, 0, 0, 0, SWP_NOOWNERZORDER | SWP_NOSIZE | SWP_NOMOVE | SWP_FRAMECHANGED);
                 },
                 _ => {}
@@ -435,21 +671,414 @@ impl TextInput {
         }
     }
 
+    /// Register the control as an OLE drop target so it can receive dropped files and text.
+    /// Must be called once the HWND exists. See `drop_target::DropTarget` for the COM object.
+    fn hook_drag_drop(&self) {
+        use winapi::um::ole2::RegisterDragDrop;
+        use winapi::um::oleidl::IDropTarget;
+        use drop_target::{DropTarget, DROP_TARGET_VTBL};
+
+        if self.handle.blank() { panic!(NOT_BOUND); }
+        let handle = self.handle.hwnd().expect(BAD_HANDLE);
+
+        let target = Box::into_raw(Box::new(DropTarget {
+            vtbl: &DROP_TARGET_VTBL,
+            refs: Cell::new(1),
+            hwnd: handle,
+        })) as *mut IDropTarget;
+
+        unsafe { RegisterDragDrop(handle, target); }
+
+        *self.drop_target.borrow_mut() = Some(target);
+    }
+
+    /// Subclass the control to filter `WM_CHAR`/`WM_PASTE` against `self.mask`.
+    /// Bound lazily the first time `set_mask` is called so a plain TextInput (the default,
+    /// `Mask::None`) pays nothing for this.
+    ///
+    /// Uses subclass id `1`: `SetWindowSubclass` replaces the reference data of an existing
+    /// `(hwnd, id)` subclass instead of stacking a second one, and `hook_non_client_size`
+    /// already subclasses this same HWND with id `0`.
+    fn hook_mask_handler(&self) {
+        use crate::bind_raw_event_handler;
+        use winapi::um::winuser::{WM_CHAR, WM_PASTE};
+
+        if self.handle.blank() { panic!(NOT_BOUND); }
+        self.handle.hwnd().expect(BAD_HANDLE);
+
+        let mask = self.mask.clone();
+
+        let handler = unsafe { bind_raw_event_handler(&self.handle, 1, move |hwnd, msg, w, _l| {
+            match msg {
+                WM_CHAR => {
+                    let c = match char::from_u32(w as u32) {
+                        Some(c) => c,
+                        None => return None
+                    };
+
+                    // Let control characters (backspace, ctrl shortcuts, ...) through untouched
+                    if (c as u32) < 0x20 {
+                        return None;
+                    }
+
+                    if !mask::char_allowed(&mask.borrow(), hwnd, c) {
+                        mask::fire_invalid_event(hwnd);
+                        return Some(0);
+                    }
+                },
+                WM_PASTE => {
+                    if let Some(text) = mask::read_clipboard_text(hwnd) {
+                        if !mask::paste_filtered(&mask.borrow(), hwnd, &text) {
+                            mask::fire_invalid_event(hwnd);
+                        }
+                    }
+
+                    // The paste was applied manually above, don't let the default proc paste again
+                    return Some(0);
+                },
+                _ => {}
+            }
+
+            None
+        }) };
+
+        *self.handler1.borrow_mut() = Some(handler);
+    }
+
 }
 
 impl Drop for TextInput {
     fn drop(&mut self) {
         use crate::unbind_raw_event_handler;
-        
+        use winapi::um::ole2::RevokeDragDrop;
+        use winapi::um::unknwnbase::IUnknown;
+        use winapi::um::wingdi::DeleteObject;
+        use winapi::shared::windef::HGDIOBJ;
+
         let handler = self.handler0.borrow();
         if let Some(h) = handler.as_ref() {
             unbind_raw_event_handler(h);
         }
-        
+
+        let handler = self.handler1.borrow();
+        if let Some(h) = handler.as_ref() {
+            unbind_raw_event_handler(h);
+        }
+
+        let handler = self.handler2.borrow();
+        if let Some(h) = handler.as_ref() {
+            unbind_raw_event_handler(h);
+        }
+
+        if let Some(target) = self.drop_target.borrow_mut().take() {
+            if let Some(handle) = self.handle.hwnd() {
+                unsafe {
+                    RevokeDragDrop(handle);
+                    (*(target as *mut IUnknown)).Release();
+                }
+            }
+        }
+
+        let background_brush = self.background_brush.get();
+        if !background_brush.is_null() {
+            unsafe { DeleteObject(background_brush as HGDIOBJ); }
+        }
+
+        let dark_brush = self.dark_brush.get();
+        if !dark_brush.is_null() {
+            unsafe { DeleteObject(dark_brush as HGDIOBJ); }
+        }
+
         self.handle.destroy();
     }
 }
 
+/// OLE `IDropTarget` implementation backing `TextInput::hook_drag_drop`.
+/// Hand rolled because the crate otherwise avoids pulling in a COM helper crate for a
+/// single interface.
+mod drop_target {
+    use winapi::shared::windef::{HWND, POINTL};
+    use winapi::shared::minwindef::{DWORD, ULONG};
+    use winapi::shared::winerror::{HRESULT, S_OK, E_NOINTERFACE, E_POINTER};
+    use winapi::shared::guiddef::REFIID;
+    use winapi::um::unknwnbase::{IUnknown, IUnknownVtbl};
+    use winapi::um::objidl::{IDataObject, FORMATETC, STGMEDIUM, TYMED_HGLOBAL, DVASPECT_CONTENT};
+    use winapi::um::oleidl::{IDropTarget, IDropTargetVtbl, DROPEFFECT_COPY};
+    use winapi::um::ole2::ReleaseStgMedium;
+    use winapi::um::shellapi::DragQueryFileW;
+    use winapi::um::winbase::{GlobalLock, GlobalUnlock};
+    use winapi::um::winuser::{CF_HDROP, CF_UNICODETEXT, EM_REPLACESEL, SendMessageW};
+    use winapi::um::shellapi::HDROP;
+    use winapi::Interface;
+    use std::os::raw::c_void;
+    use std::cell::Cell;
+    use std::{mem, ptr};
+    use super::ControlHandle;
+
+    #[repr(C)]
+    pub struct DropTarget {
+        pub vtbl: *const IDropTargetVtbl,
+        pub refs: Cell<u32>,
+        pub hwnd: HWND,
+    }
+
+    pub static DROP_TARGET_VTBL: IDropTargetVtbl = IDropTargetVtbl {
+        parent: IUnknownVtbl {
+            QueryInterface: query_interface,
+            AddRef: add_ref,
+            Release: release,
+        },
+        DragEnter: drag_enter,
+        DragOver: drag_over,
+        DragLeave: drag_leave,
+        Drop: on_drop,
+    };
+
+    unsafe extern "system" fn query_interface(this: *mut IUnknown, riid: REFIID, out: *mut *mut c_void) -> HRESULT {
+        if out.is_null() { return E_POINTER; }
+
+        if *riid == IUnknown::uuidof() || *riid == IDropTarget::uuidof() {
+            add_ref(this);
+            *out = this as *mut c_void;
+            S_OK
+        } else {
+            *out = ptr::null_mut();
+            E_NOINTERFACE
+        }
+    }
+
+    unsafe extern "system" fn add_ref(this: *mut IUnknown) -> ULONG {
+        let target = &*(this as *mut DropTarget);
+        let count = target.refs.get() + 1;
+        target.refs.set(count);
+        count as ULONG
+    }
+
+    unsafe extern "system" fn release(this: *mut IUnknown) -> ULONG {
+        let target = &*(this as *mut DropTarget);
+        let count = target.refs.get() - 1;
+        target.refs.set(count);
+        if count == 0 {
+            let _ = Box::from_raw(this as *mut DropTarget);
+        }
+        count as ULONG
+    }
+
+    unsafe extern "system" fn drag_enter(_this: *mut IDropTarget, _data: *const IDataObject, _keys: DWORD, _pt: *const POINTL, effect: *mut DWORD) -> HRESULT {
+        if !effect.is_null() { *effect = DROPEFFECT_COPY; }
+        S_OK
+    }
+
+    unsafe extern "system" fn drag_over(_this: *mut IDropTarget, _keys: DWORD, _pt: *const POINTL, effect: *mut DWORD) -> HRESULT {
+        if !effect.is_null() { *effect = DROPEFFECT_COPY; }
+        S_OK
+    }
+
+    unsafe extern "system" fn drag_leave(_this: *mut IDropTarget) -> HRESULT {
+        S_OK
+    }
+
+    unsafe extern "system" fn on_drop(this: *mut IDropTarget, data: *const IDataObject, _keys: DWORD, _pt: *const POINTL, effect: *mut DWORD) -> HRESULT {
+        let target = &*(this as *mut DropTarget);
+        handle_dropped_data(target.hwnd, data);
+        if !effect.is_null() { *effect = DROPEFFECT_COPY; }
+        S_OK
+    }
+
+    /// Pull `CF_HDROP` (file paths) or `CF_UNICODETEXT` (plain text) out of the dropped
+    /// `IDataObject` and hand it to the control: files fire `OnFileDrop`, text is inserted
+    /// at the current selection (same as a paste) and fires `OnTextDrop`.
+    unsafe fn handle_dropped_data(hwnd: HWND, data: *const IDataObject) {
+        let data = &*data;
+
+        let mut file_fmt = FORMATETC {
+            cfFormat: CF_HDROP as u16,
+            ptd: ptr::null(),
+            dwAspect: DVASPECT_CONTENT,
+            lindex: -1,
+            tymed: TYMED_HGLOBAL,
+        };
+        let mut medium: STGMEDIUM = mem::zeroed();
+
+        if data.GetData(&mut file_fmt, &mut medium) == S_OK {
+            let hdrop = *(*medium.u).hGlobal() as HDROP;
+            let count = DragQueryFileW(hdrop, 0xFFFFFFFF, ptr::null_mut(), 0);
+
+            let mut files = Vec::with_capacity(count as usize);
+            for i in 0..count {
+                let len = DragQueryFileW(hdrop, i, ptr::null_mut(), 0);
+                let mut buffer: Vec<u16> = vec![0; (len + 1) as usize];
+                DragQueryFileW(hdrop, i, buffer.as_mut_ptr(), len + 1);
+                files.push(String::from_utf16_lossy(&buffer[..len as usize]));
+            }
+
+            ReleaseStgMedium(&mut medium);
+
+            if !files.is_empty() {
+                fire_event(hwnd, files);
+            }
+
+            return;
+        }
+
+        let mut text_fmt = FORMATETC {
+            cfFormat: CF_UNICODETEXT as u16,
+            ptd: ptr::null(),
+            dwAspect: DVASPECT_CONTENT,
+            lindex: -1,
+            tymed: TYMED_HGLOBAL,
+        };
+        let mut medium: STGMEDIUM = mem::zeroed();
+
+        if data.GetData(&mut text_fmt, &mut medium) == S_OK {
+            let hglobal = *(*medium.u).hGlobal();
+            let wide = GlobalLock(hglobal) as *const u16;
+
+            if !wide.is_null() {
+                let len = (0..).take_while(|&i| *wide.offset(i) != 0).count();
+                let text = String::from_utf16_lossy(std::slice::from_raw_parts(wide, len));
+                GlobalUnlock(hglobal);
+
+                // Insert the dropped text at the current selection, same as a paste
+                let mut buffer: Vec<u16> = text.encode_utf16().collect();
+                buffer.push(0);
+                SendMessageW(hwnd, EM_REPLACESEL, 1, buffer.as_ptr() as isize);
+
+                fire_text_event(hwnd, text);
+            }
+
+            ReleaseStgMedium(&mut medium);
+        }
+    }
+
+    fn fire_event(hwnd: HWND, files: Vec<String>) {
+        use crate::win32::window::handle_events;
+        use crate::{Event, EventData};
+
+        handle_events(ControlHandle::Hwnd(hwnd), Event::OnFileDrop, EventData::OnFileDrop(files));
+    }
+
+    fn fire_text_event(hwnd: HWND, text: String) {
+        use crate::win32::window::handle_events;
+        use crate::{Event, EventData};
+
+        handle_events(ControlHandle::Hwnd(hwnd), Event::OnTextDrop, EventData::OnTextDrop(text));
+    }
+}
+
+/// Character filtering helpers backing `TextInput::set_mask`.
+mod mask {
+    use winapi::shared::windef::HWND;
+    use winapi::shared::minwindef::{WPARAM, LPARAM};
+    use winapi::um::winuser::{EM_GETSEL, EM_REPLACESEL, OpenClipboard, CloseClipboard, GetClipboardData, CF_UNICODETEXT};
+    use winapi::um::winbase::{GlobalLock, GlobalUnlock};
+    use super::{wh, ControlHandle, Mask};
+
+    fn caret_position(hwnd: HWND) -> usize {
+        let mut start = 0u32;
+        let ptr = &mut start as *mut u32;
+        wh::send_message(hwnd, EM_GETSEL as u32, ptr as WPARAM, 0);
+        start as usize
+    }
+
+    fn replace_selection(hwnd: HWND, text: &str) {
+        let mut buffer: Vec<u16> = text.encode_utf16().collect();
+        buffer.push(0);
+        wh::send_message(hwnd, EM_REPLACESEL as u32, 1, buffer.as_ptr() as LPARAM);
+    }
+
+    fn slot_allows(slot: char, c: char) -> bool {
+        match slot {
+            '#' => c.is_ascii_digit(),
+            '@' => c.is_ascii_alphabetic(),
+            '*' => c.is_ascii_alphanumeric(),
+            _ => false,
+        }
+    }
+
+    /// Insert the literal characters that sit right after the caret in `template`,
+    /// advancing the caret past them so the next typed/pasted character lands on a
+    /// fillable slot.
+    fn skip_literals(template: &[char], hwnd: HWND) {
+        loop {
+            match template.get(caret_position(hwnd)) {
+                Some(&c) if c != '#' && c != '@' && c != '*' => replace_selection(hwnd, &c.to_string()),
+                _ => break,
+            }
+        }
+    }
+
+    /// Return true if `c` is accepted by `mask` for the control at `hwnd`. For a `Template`
+    /// mask this also inserts any literal separators at the caret and moves the caret past them.
+    pub fn char_allowed(mask: &Mask, hwnd: HWND, c: char) -> bool {
+        match mask {
+            Mask::None => true,
+            Mask::Digits => c.is_ascii_digit(),
+            Mask::Alphanumeric => c.is_ascii_alphanumeric(),
+            Mask::Custom(f) => f(c),
+            Mask::Template(template) => {
+                let template: Vec<char> = template.chars().collect();
+                skip_literals(&template, hwnd);
+                match template.get(caret_position(hwnd)) {
+                    Some(&slot) => slot_allows(slot, c),
+                    None => false,
+                }
+            }
+        }
+    }
+
+    /// Filter a block of pasted text through `mask`, inserting the accepted characters at
+    /// the current selection one at a time. Returns true if every character was accepted.
+    pub fn paste_filtered(mask: &Mask, hwnd: HWND, text: &str) -> bool {
+        let mut all_accepted = true;
+
+        for c in text.chars() {
+            if char_allowed(mask, hwnd, c) {
+                replace_selection(hwnd, &c.to_string());
+            } else {
+                all_accepted = false;
+            }
+        }
+
+        all_accepted
+    }
+
+    /// Read the clipboard as `CF_UNICODETEXT`. Returns `None` if the clipboard could not be
+    /// opened or doesn't hold text.
+    pub fn read_clipboard_text(hwnd: HWND) -> Option<String> {
+        unsafe {
+            if OpenClipboard(hwnd) == 0 {
+                return None;
+            }
+
+            let data = GetClipboardData(CF_UNICODETEXT as u32);
+            let text = if data.is_null() {
+                None
+            } else {
+                let ptr = GlobalLock(data as _) as *const u16;
+                if ptr.is_null() {
+                    None
+                } else {
+                    let len = (0..).take_while(|&i| *ptr.offset(i) != 0).count();
+                    let text = String::from_utf16_lossy(std::slice::from_raw_parts(ptr, len));
+                    GlobalUnlock(data as _);
+                    Some(text)
+                }
+            };
+
+            CloseClipboard();
+            text
+        }
+    }
+
+    pub fn fire_invalid_event(hwnd: HWND) {
+        use crate::win32::window::handle_events;
+        use crate::{Event, EventData};
+
+        handle_events(ControlHandle::Hwnd(hwnd), Event::OnTextInputInvalid, EventData::NoData);
+    }
+}
+
 pub struct TextInputBuilder<'a> {
     text: &'a str,
     size: (i32, i32),
@@ -462,6 +1091,9 @@ pub struct TextInputBuilder<'a> {
     font: Option<&'a Font>,
     parent: Option<ControlHandle>,
     background_color: Option<[u8; 3]>,
+    placeholder: Option<&'a str>,
+    dark_mode: bool,
+    mask: Mask,
 }
 
 impl<'a> TextInputBuilder<'a> {
@@ -516,6 +1148,21 @@ impl<'a> TextInputBuilder<'a> {
         self
     }
 
+    pub fn placeholder(mut self, text: Option<&'a str>) -> TextInputBuilder<'a> {
+        self.placeholder = text;
+        self
+    }
+
+    pub fn dark_mode(mut self, dark: bool) -> TextInputBuilder<'a> {
+        self.dark_mode = dark;
+        self
+    }
+
+    pub fn mask(mut self, mask: Mask) -> TextInputBuilder<'a> {
+        self.mask = mask;
+        self
+    }
+
     pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> TextInputBuilder<'a> {
         self.parent = Some(p.into());
         self
@@ -552,6 +1199,7 @@ impl<'a> TextInputBuilder<'a> {
             .build()?;
 
         out.hook_non_client_size(self.background_color);
+        out.hook_drag_drop();
 
         if self.limit > 0 {
             out.set_limit(self.limit);
@@ -569,6 +1217,18 @@ impl<'a> TextInputBuilder<'a> {
             out.set_font(self.font);
         }
 
+        if self.placeholder.is_some() {
+            out.set_placeholder(self.placeholder);
+        }
+
+        if self.dark_mode {
+            out.set_dark_mode(self.dark_mode);
+        }
+
+        if let Mask::None = self.mask {} else {
+            out.set_mask(self.mask);
+        }
+
         Ok(())
     }
 
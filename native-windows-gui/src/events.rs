@@ -0,0 +1,31 @@
+/*!
+    Events are how a control reports activity back to the application, through `Event` (what
+    happened) paired with `EventData` (the payload, if any).
+
+    This file only lists the variants introduced by the `TextInput` drag-and-drop and input-mask
+    work; the full `Event`/`EventData` enums carry many more control-specific variants elsewhere
+    in the crate (`OnInit`, `OnButtonClick`, etc.) that are out of scope for this patch.
+*/
+
+/// Identifies what happened to a control. Paired with `EventData`, which carries the payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// One or more files were dropped on a control registered as an OLE drop target
+    OnFileDrop,
+    /// Text was dropped on a control registered as an OLE drop target
+    OnTextDrop,
+    /// A keystroke or paste was rejected by a `TextInput` mask set with `set_mask`
+    OnTextInputInvalid,
+}
+
+/// Payload carried alongside an `Event`. Not every event carries data; `NoData` is used
+/// for those that don't.
+#[derive(Debug)]
+pub enum EventData {
+    /// No payload
+    NoData,
+    /// Dropped file paths, see `Event::OnFileDrop`
+    OnFileDrop(Vec<String>),
+    /// Dropped text, see `Event::OnTextDrop`
+    OnTextDrop(String),
+}